@@ -0,0 +1,326 @@
+use iced_web::{Application, Column, Command, Element};
+use wasm_bindgen::{closure::Closure, JsCast, JsValue};
+use wasm_bindgen_futures::JsFuture;
+use web_sys::FileReader;
+
+use crate::diag::{self, Segment};
+use crate::widget::{FileDrop, HexView, Link, Pre, TextArea};
+use crate::{fetch_bytes, ParseError, UrlError};
+
+/// The decoded view of the current input: the raw bytes, the canonical
+/// hex, and the diagnostic-notation segments (each tagged with the byte
+/// range it came from), or the offset the parse failed at plus a message.
+type DecodeResult = Result<(Vec<u8>, String, Vec<Segment>), ParseError>;
+
+pub struct App {
+    input: String,
+    /// The URL field, fetched on submit via [`fetch_bytes`].
+    url: String,
+    /// Bumped on every `InputChanged`, so a `Decoded` message that resolves
+    /// after the user has kept typing can be told apart from the one that
+    /// matches what's currently in the box.
+    generation: u64,
+    output: Option<DecodeResult>,
+    /// The byte range currently hovered in either pane, highlighted in both.
+    hover: Option<(usize, usize)>,
+}
+
+#[derive(Debug, Clone)]
+pub enum Message {
+    InputChanged(String),
+    FileDropped(web_sys::File),
+    UrlChanged(String),
+    UrlSubmitted,
+    /// `sync_input` is true when the `TextArea` should be overwritten with
+    /// the resulting canonical hex once this resolves -- the input didn't
+    /// come from the `TextArea` itself (a dropped file, a fetched URL, a
+    /// permalink), so there's nothing in it yet to clobber.
+    Decoded {
+        generation: u64,
+        result: DecodeResult,
+        sync_input: bool,
+    },
+    HoverChanged(Option<(usize, usize)>),
+}
+
+impl Application for App {
+    type Message = Message;
+
+    fn new() -> (Self, Command<Message>) {
+        let mut app = Self {
+            input: String::new(),
+            url: String::new(),
+            generation: 0,
+            output: None,
+            hover: None,
+        };
+
+        match hex_from_fragment() {
+            None => (app, Command::none()),
+            Some(hex) => {
+                app.generation += 1;
+                let generation = app.generation;
+                let command = Command::perform(decode(hex), move |result| Message::Decoded {
+                    generation,
+                    result,
+                    sync_input: true,
+                });
+                (app, command)
+            }
+        }
+    }
+
+    fn title(&self) -> String {
+        String::from("cbor.nemo157.com")
+    }
+
+    fn update(&mut self, message: Message) -> Command<Message> {
+        match message {
+            Message::InputChanged(input) => {
+                self.generation += 1;
+                let generation = self.generation;
+                self.input = input.clone();
+                self.hover = None;
+                Command::perform(decode(input), move |result| Message::Decoded {
+                    generation,
+                    result,
+                    sync_input: false,
+                })
+            }
+            Message::FileDropped(file) => {
+                self.generation += 1;
+                let generation = self.generation;
+                self.input = String::new();
+                self.hover = None;
+                Command::perform(decode_file(file), move |result| Message::Decoded {
+                    generation,
+                    result,
+                    sync_input: true,
+                })
+            }
+            Message::UrlChanged(url) => {
+                self.url = url;
+                Command::none()
+            }
+            Message::UrlSubmitted => {
+                self.generation += 1;
+                let generation = self.generation;
+                self.hover = None;
+                Command::perform(decode_url(self.url.clone()), move |result| Message::Decoded {
+                    generation,
+                    result,
+                    sync_input: true,
+                })
+            }
+            Message::Decoded {
+                generation,
+                result,
+                sync_input,
+            } => {
+                if generation == self.generation {
+                    if let Ok((_bytes, hex, _segments)) = &result {
+                        set_fragment(hex);
+                        if sync_input {
+                            self.input = hex.clone();
+                        }
+                    }
+                    self.output = Some(result);
+                }
+                Command::none()
+            }
+            Message::HoverChanged(hover) => {
+                self.hover = hover;
+                Command::none()
+            }
+        }
+    }
+
+    fn view(&mut self) -> Element<Message> {
+        let (bytes, segments, caret, permalink) = match &self.output {
+            None => (Vec::new(), Vec::new(), None, None),
+            Some(Ok((bytes, hex, segments))) => {
+                (bytes.clone(), segments.clone(), None, Some(fragment_href(hex)))
+            }
+            Some(Err(err)) => (Vec::new(), Vec::new(), Some(caret_under(&self.input, err)), None),
+        };
+
+        let mut column = Column::new()
+            .push(TextArea::new(
+                "paste hex or diagnostic notation CBOR",
+                &self.input,
+                Message::InputChanged,
+            ))
+            .push(FileDrop::new(Message::FileDropped))
+            .push(
+                TextArea::new(
+                    "or paste a URL to an application/cbor resource, then Ctrl/Cmd+Enter",
+                    &self.url,
+                    Message::UrlChanged,
+                )
+                .on_submit(Message::UrlSubmitted),
+            );
+
+        if let Some(caret) = caret {
+            column = column.push(Pre::new(caret));
+        }
+
+        if let Some(href) = permalink {
+            column = column.push(Link::new(Pre::new("copy permalink"), &href));
+        }
+
+        column
+            .push(HexView::new(bytes, Message::HoverChanged).highlight(self.hover))
+            .push(
+                Pre::segmented(segments)
+                    .highlight(self.hover)
+                    .on_hover(Message::HoverChanged),
+            )
+            .into()
+    }
+}
+
+/// The offending line of `input`, followed by a line of spaces with a `^`
+/// under the column the error was detected at, followed by the parser's
+/// message. `input` is multi-line (diagnostic notation is commonly
+/// indented/wrapped), so the offset has to be resolved to a (line, column)
+/// pair first rather than treated as a column on a single line.
+fn caret_under(input: &str, err: &ParseError) -> String {
+    if input.is_empty() {
+        return err.message.clone();
+    }
+
+    let mut column = err.offset;
+    let mut line = "";
+    for candidate in input.split('\n') {
+        line = candidate;
+        let len = candidate.chars().count();
+        if column <= len {
+            break;
+        }
+        column -= len + 1; // account for the newline this split ate
+    }
+
+    format!("{}\n{}^\n{}", line, " ".repeat(column), err.message)
+}
+
+/// The `href` for a "copy permalink" link: the canonical hex, base64url
+/// encoded into the URL fragment.
+fn fragment_href(hex: &str) -> String {
+    format!("#{}", base64::encode_config(hex, base64::URL_SAFE_NO_PAD))
+}
+
+/// Mirrors `hex` into the URL fragment without adding a history entry, so
+/// the address bar always matches what's on screen.
+fn set_fragment(hex: &str) {
+    let window = web_sys::window().expect("no global `window` exists");
+    let history = window.history().expect("no `history` available");
+    let _ = history.replace_state_with_url(&JsValue::NULL, "", Some(&fragment_href(hex)));
+}
+
+/// Reads the hex encoded into the URL fragment on startup, if any.
+fn hex_from_fragment() -> Option<String> {
+    let window = web_sys::window().expect("no global `window` exists");
+    let fragment = window.location().hash().ok()?;
+    let encoded = fragment.strip_prefix('#')?;
+    if encoded.is_empty() {
+        return None;
+    }
+    let bytes = base64::decode_config(encoded, base64::URL_SAFE_NO_PAD).ok()?;
+    String::from_utf8(bytes).ok()
+}
+
+/// `async` here buys `Command::perform` a microtask boundary to resolve on
+/// -- paired with the `generation` counter, that's enough to drop a decode
+/// that's gone stale before it returned. `cbor_diag`'s parser itself has no
+/// `.await` point and runs to completion in one go, so this doesn't chunk or
+/// yield mid-parse; a large enough input still blocks the UI thread while it
+/// runs, same as calling it synchronously would.
+async fn decode(input: String) -> DecodeResult {
+    cbor_diag::parse_hex(&input)
+        .or_else(|_| cbor_diag::parse_diag(&input))
+        .map(|v| (v.to_bytes(), v.to_hex(), diag::segments(&v)))
+        .map_err(|e| ParseError {
+            offset: e.offset(),
+            message: e.to_string(),
+        })
+}
+
+/// See [`decode`]: the network fetch genuinely yields, but the parse once
+/// the bytes are in hand doesn't.
+async fn decode_url(url: String) -> DecodeResult {
+    let bytes = fetch_bytes(&url).await.map_err(url_parse_error)?;
+    cbor_diag::parse_bytes(&bytes)
+        .map(|v| (v.to_bytes(), v.to_hex(), diag::segments(&v)))
+        .map_err(|e| ParseError {
+            offset: e.offset(),
+            message: e.to_string(),
+        })
+}
+
+/// Folds network/HTTP failures into the same `ParseError` shape the rest of
+/// the panes render, since there's no byte offset to point a caret at for
+/// those -- only the message is meaningful.
+fn url_parse_error(err: UrlError) -> ParseError {
+    match err {
+        UrlError::Network(message) => ParseError { offset: 0, message },
+        UrlError::Http { status, status_text } => ParseError {
+            offset: 0,
+            message: format!("{} {}", status, status_text),
+        },
+        UrlError::Parse(err) => err,
+    }
+}
+
+/// See [`decode`]: the file read genuinely yields, but the parse once the
+/// bytes are in hand doesn't.
+async fn decode_file(file: web_sys::File) -> DecodeResult {
+    let bytes = read_file(file).await.map_err(|message| ParseError {
+        offset: 0,
+        message,
+    })?;
+    cbor_diag::parse_bytes(&bytes)
+        .map(|v| (v.to_bytes(), v.to_hex(), diag::segments(&v)))
+        .map_err(|e| ParseError {
+            offset: e.offset(),
+            message: e.to_string(),
+        })
+}
+
+/// Reads a dropped/selected `File` to completion via the `FileReader` API,
+/// which is callback-based rather than `Promise`-based. A read failure
+/// (permission revoked, I/O error, ...) is reported back rather than
+/// panicking.
+async fn read_file(file: web_sys::File) -> Result<Vec<u8>, String> {
+    let reader = FileReader::new().map_err(|e| format!("{:?}", e))?;
+
+    let promise = js_sys::Promise::new(&mut |resolve, reject| {
+        let onload_reader = reader.clone();
+        let onload = Closure::once(move || {
+            let result = onload_reader.result().unwrap_or(JsValue::NULL);
+            let _ = resolve.call1(&JsValue::NULL, &result);
+        });
+        reader.set_onload(Some(onload.as_ref().unchecked_ref()));
+        onload.forget();
+
+        let onerror_reader = reader.clone();
+        let onerror = Closure::once(move || {
+            let message = onerror_reader
+                .error()
+                .map(|error| error.message())
+                .unwrap_or_else(|| String::from("unknown error reading file"));
+            let _ = reject.call1(&JsValue::NULL, &JsValue::from_str(&message));
+        });
+        reader.set_onerror(Some(onerror.as_ref().unchecked_ref()));
+        onerror.forget();
+    });
+
+    reader
+        .read_as_array_buffer(&file)
+        .map_err(|e| format!("{:?}", e))?;
+
+    let buffer = JsFuture::from(promise)
+        .await
+        .map_err(|e| e.as_string().unwrap_or_else(|| format!("{:?}", e)))?;
+
+    Ok(js_sys::Uint8Array::new(&buffer).to_vec())
+}
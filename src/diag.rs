@@ -0,0 +1,115 @@
+//! Renders a decoded [`cbor_diag::Value`] to diagnostic notation ourselves,
+//! tagging each token with the byte range it was decoded from.
+//!
+//! `cbor_diag::Value::to_diag_pretty_colored_html` only returns a flat HTML
+//! string with no offset information, so there's nothing for the widget
+//! layer to hang `data-start`/`data-end` attributes off of. Walking the
+//! value here and having each level report back how many bytes it consumed
+//! gives the widgets real spans to work with, without re-serializing the
+//! same subtree once per ancestor.
+
+use cbor_diag::Value;
+
+/// A chunk of diagnostic-notation text, tagged with the byte range (within
+/// the encoded item) it renders, if any. An opening bracket (or a tag's
+/// `N(`) carries the span of the whole container; separators and closing
+/// brackets carry no span of their own.
+#[derive(Clone, Debug)]
+pub(crate) struct Segment {
+    pub text: String,
+    pub class: Option<&'static str>,
+    pub span: Option<(usize, usize)>,
+}
+
+pub(crate) fn segments(value: &Value) -> Vec<Segment> {
+    let mut segments = Vec::new();
+    render(value, 0, &mut segments);
+    segments
+}
+
+fn push(segments: &mut Vec<Segment>, text: impl Into<String>, class: Option<&'static str>, span: Option<(usize, usize)>) {
+    segments.push(Segment {
+        text: text.into(),
+        class,
+        span,
+    });
+}
+
+/// Appends `value`'s segments at `offset` and returns the number of bytes it
+/// encodes to, so callers can advance their own cursor without re-deriving
+/// it from a fresh `to_bytes()` call -- for a deeply nested value that would
+/// re-serialize the same inner bytes once per ancestor.
+fn render(value: &Value, offset: usize, segments: &mut Vec<Segment>) -> usize {
+    match value {
+        Value::Array(items) => {
+            let open = segments.len();
+            push(segments, "[", Some("container"), None);
+            let mut cursor = offset + header_len(items.len() as u64);
+            for (i, item) in items.iter().enumerate() {
+                if i > 0 {
+                    push(segments, ", ", None, None);
+                }
+                cursor += render(item, cursor, segments);
+            }
+            push(segments, "]", None, None);
+            let len = cursor - offset;
+            segments[open].span = Some((offset, offset + len));
+            len
+        }
+        Value::Map(entries) => {
+            let open = segments.len();
+            push(segments, "{", Some("container"), None);
+            let mut cursor = offset + header_len(entries.len() as u64);
+            for (i, (key, value)) in entries.iter().enumerate() {
+                if i > 0 {
+                    push(segments, ", ", None, None);
+                }
+                cursor += render(key, cursor, segments);
+                push(segments, ": ", None, None);
+                cursor += render(value, cursor, segments);
+            }
+            push(segments, "}", None, None);
+            let len = cursor - offset;
+            segments[open].span = Some((offset, offset + len));
+            len
+        }
+        Value::Tag(tag, inner) => {
+            let header = header_len(*tag);
+            let open = segments.len();
+            push(segments, format!("{}(", tag), Some("tag"), None);
+            let inner_len = render(inner, offset + header, segments);
+            push(segments, ")", None, None);
+            let len = header + inner_len;
+            segments[open].span = Some((offset, offset + len));
+            len
+        }
+        leaf => {
+            let len = value.to_bytes().len();
+            push(segments, leaf.to_diag(), Some(leaf_class(leaf)), Some((offset, offset + len)));
+            len
+        }
+    }
+}
+
+fn leaf_class(value: &Value) -> &'static str {
+    match value {
+        Value::Unsigned(_) | Value::Negative(_) => "number",
+        Value::ByteString(_) => "bytestring",
+        Value::TextString(_) => "textstring",
+        Value::Float(_) => "float",
+        _ => "simple",
+    }
+}
+
+/// Length, in bytes, of a CBOR item's major-type header for a given
+/// argument value (item count, tag number, ...): one byte for arguments
+/// under 24, then 1/2/4/8 extra bytes for the next size classes.
+fn header_len(argument: u64) -> usize {
+    match argument {
+        0..=23 => 1,
+        24..=0xff => 2,
+        0x100..=0xffff => 3,
+        0x1_0000..=0xffff_ffff => 5,
+        _ => 9,
+    }
+}
@@ -1,11 +1,38 @@
-use wasm_bindgen::{prelude::wasm_bindgen, JsValue};
+mod app;
+mod diag;
+mod widget;
+
+use iced_web::Application;
+use wasm_bindgen::{prelude::wasm_bindgen, JsCast, JsValue};
+use wasm_bindgen_futures::{future_to_promise, JsFuture};
+use web_sys::{Request, RequestInit, RequestMode, Response};
+
+#[wasm_bindgen(start)]
+pub fn start() {
+    app::App::run();
+}
+
+/// A parse failure with the byte/char offset it was detected at, so the
+/// UI can place a caret under the exact spot that broke.
+#[derive(serde::Serialize, Clone, Debug)]
+pub(crate) struct ParseError {
+    pub offset: usize,
+    pub message: String,
+}
+
+fn parse_error(e: cbor_diag::Error) -> ParseError {
+    ParseError {
+        offset: e.offset(),
+        message: e.to_string(),
+    }
+}
 
 #[wasm_bindgen]
 pub fn parse_auto(s: &str) -> JsValue {
     let result = cbor_diag::parse_hex(s)
         .or_else(|_| cbor_diag::parse_diag(s))
         .map(|v| (v.to_hex(), v.to_diag_pretty_colored_html()))
-        .map_err(|e| format!("{:?}", e));
+        .map_err(parse_error);
     JsValue::from_serde(&result).unwrap()
 }
 
@@ -13,7 +40,7 @@ pub fn parse_auto(s: &str) -> JsValue {
 pub fn parse_hex(hex: &str) -> JsValue {
     let result = cbor_diag::parse_hex(hex)
         .map(|v| (v.to_hex(), v.to_diag_pretty_colored_html()))
-        .map_err(|e| format!("{:?}", e));
+        .map_err(parse_error);
     JsValue::from_serde(&result).unwrap()
 }
 
@@ -21,6 +48,76 @@ pub fn parse_hex(hex: &str) -> JsValue {
 pub fn parse_diag(diag: &str) -> JsValue {
     let result = cbor_diag::parse_diag(diag)
         .map(|v| (v.to_hex(), v.to_diag_pretty_colored_html()))
-        .map_err(|e| format!("{:?}", e));
+        .map_err(parse_error);
     JsValue::from_serde(&result).unwrap()
 }
+
+/// Error fetching a remote resource, distinguishing failures that happened
+/// talking to the network/server from failures decoding what came back.
+#[derive(serde::Serialize, Clone, Debug)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub(crate) enum UrlError {
+    Network(String),
+    Http { status: u16, status_text: String },
+    Parse(ParseError),
+}
+
+/// Fetch a remote resource and decode it as CBOR, without requiring the
+/// caller to hex-dump it first.
+///
+/// Resolves to the same `(hex, colored_html)` tuple as `parse_auto`/
+/// `parse_hex`/`parse_diag`, but the raw response bytes are fed directly
+/// into `cbor_diag`'s byte decoder. It's exported so callers can embed the
+/// decoder without going through the page at all; the `App`'s own URL field
+/// doesn't call it, it shares [`fetch_bytes`] instead.
+#[wasm_bindgen]
+pub fn parse_url(url: &str) -> js_sys::Promise {
+    let url = url.to_owned();
+    future_to_promise(async move {
+        let result = fetch_bytes(&url)
+            .await
+            .and_then(|bytes| {
+                cbor_diag::parse_bytes(&bytes)
+                    .map(|v| (v.to_hex(), v.to_diag_pretty_colored_html()))
+                    .map_err(|e| UrlError::Parse(parse_error(e)))
+            });
+        Ok(JsValue::from_serde(&result).unwrap())
+    })
+}
+
+/// Fetches the raw bytes at `url`, without decoding them. Shared by
+/// [`parse_url`] and [`app::App`]'s own URL field, which decodes into
+/// diagnostic-notation segments rather than the flat HTML `parse_url`
+/// returns over the wasm boundary.
+pub(crate) async fn fetch_bytes(url: &str) -> Result<Vec<u8>, UrlError> {
+    let mut opts = RequestInit::new();
+    opts.method("GET");
+    opts.mode(RequestMode::Cors);
+
+    let request = Request::new_with_str_and_init(url, &opts)
+        .map_err(|e| UrlError::Network(format!("{:?}", e)))?;
+
+    let window = web_sys::window().expect("no global `window` exists");
+    let response = JsFuture::from(window.fetch_with_request(&request))
+        .await
+        .map_err(|e| UrlError::Network(format!("{:?}", e)))?
+        .dyn_into::<Response>()
+        .expect("fetch did not resolve to a Response");
+
+    if !response.ok() {
+        return Err(UrlError::Http {
+            status: response.status(),
+            status_text: response.status_text(),
+        });
+    }
+
+    let buffer = JsFuture::from(
+        response
+            .array_buffer()
+            .map_err(|e| UrlError::Network(format!("{:?}", e)))?,
+    )
+    .await
+    .map_err(|e| UrlError::Network(format!("{:?}", e)))?;
+
+    Ok(js_sys::Uint8Array::new(&buffer).to_vec())
+}
@@ -0,0 +1,92 @@
+use std::rc::Rc;
+
+use bumpalo::Bump;
+use dodrio::Node;
+use iced_web::{style::Sheet, Bus, Element, Widget};
+
+/// A drop target, sibling to [`super::TextArea`], for picking a binary CBOR
+/// document straight off disk instead of pasting its hex dump.
+pub struct FileDrop<Message> {
+    on_file: Rc<Box<dyn Fn(web_sys::File) -> Message>>,
+}
+
+impl<Message> FileDrop<Message> {
+    pub fn new(on_file: impl Fn(web_sys::File) -> Message + 'static) -> Self {
+        Self {
+            on_file: Rc::new(Box::new(on_file)),
+        }
+    }
+}
+
+impl<Message> Widget<Message> for FileDrop<Message>
+where
+    Message: 'static + Clone,
+{
+    fn node<'b>(
+        &self,
+        bump: &'b Bump,
+        bus: &Bus<Message>,
+        _style_sheet: &mut Sheet<'b>,
+    ) -> Node<'b> {
+        use wasm_bindgen::JsCast;
+
+        let change_bus = bus.clone();
+        let on_change = self.on_file.clone();
+        let drop_bus = bus.clone();
+        let on_drop = self.on_file.clone();
+
+        dodrio::builder::div(bump)
+            .attr("class", "file-drop")
+            .children(vec![
+                dodrio::builder::text("drop a CBOR file here, or "),
+                dodrio::builder::input(bump)
+                    .attr("type", "file")
+                    .on("change", move |root, vdom, event| {
+                        let input = match event
+                            .target()
+                            .and_then(|t| t.dyn_into::<web_sys::HtmlInputElement>().ok())
+                        {
+                            None => return,
+                            Some(input) => input,
+                        };
+                        let file = match input.files().and_then(|files| files.get(0)) {
+                            None => return,
+                            Some(file) => file,
+                        };
+                        change_bus.publish(on_change(file), root);
+                        vdom.schedule_render();
+                    })
+                    .finish(),
+            ])
+            .on("dragover", |_root, _vdom, event| {
+                event.prevent_default();
+            })
+            .on("drop", move |root, vdom, event| {
+                event.prevent_default();
+                let event = match event.dyn_into::<web_sys::DragEvent>().ok() {
+                    None => return,
+                    Some(event) => event,
+                };
+                let file = match event
+                    .data_transfer()
+                    .and_then(|data| data.files())
+                    .and_then(|files| files.get(0))
+                {
+                    None => return,
+                    Some(file) => file,
+                };
+                drop_bus.publish(on_drop(file), root);
+                vdom.schedule_render();
+            })
+            .finish()
+    }
+}
+
+impl<'a, Message> From<FileDrop<Message>> for Element<'a, Message>
+where
+    Message: 'static + Clone,
+{
+    fn from(file_drop: FileDrop<Message>) -> Element<'a, Message> {
+        Element::new(file_drop)
+    }
+}
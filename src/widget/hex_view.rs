@@ -0,0 +1,107 @@
+use std::rc::Rc;
+
+use bumpalo::Bump;
+use dodrio::Node;
+use iced_web::{style::Sheet, Bus, Element, Widget};
+
+/// Renders raw bytes as a hex dump where each byte is its own `<span>`
+/// tagged with `data-start`/`data-end`, so it can be cross-highlighted
+/// against the diagnostic-notation pane that decoded it.
+pub struct HexView<Message> {
+    bytes: Vec<u8>,
+    highlight: Option<(usize, usize)>,
+    on_hover: Rc<Box<dyn Fn(Option<(usize, usize)>) -> Message>>,
+}
+
+impl<Message> HexView<Message> {
+    pub fn new(
+        bytes: impl Into<Vec<u8>>,
+        on_hover: impl Fn(Option<(usize, usize)>) -> Message + 'static,
+    ) -> Self {
+        Self {
+            bytes: bytes.into(),
+            highlight: None,
+            on_hover: Rc::new(Box::new(on_hover)),
+        }
+    }
+
+    pub fn highlight(mut self, range: Option<(usize, usize)>) -> Self {
+        self.highlight = range;
+        self
+    }
+}
+
+impl<Message> Widget<Message> for HexView<Message>
+where
+    Message: 'static + Clone,
+{
+    fn node<'b>(
+        &self,
+        bump: &'b Bump,
+        bus: &Bus<Message>,
+        _style_sheet: &mut Sheet<'b>,
+    ) -> Node<'b> {
+        let children = self
+            .bytes
+            .iter()
+            .enumerate()
+            .map(|(start, byte)| {
+                let end = start + 1;
+                let highlighted = self
+                    .highlight
+                    .map_or(false, |range| super::span_within((start, end), range));
+                dodrio::builder::span(bump)
+                    .attr(
+                        "class",
+                        if highlighted { "hex-byte highlighted" } else { "hex-byte" },
+                    )
+                    .attr("data-start", bumpalo::format!(in bump, "{}", start).into_bump_str())
+                    .attr("data-end", bumpalo::format!(in bump, "{}", end).into_bump_str())
+                    .children(vec![dodrio::builder::text(
+                        bumpalo::format!(in bump, "{:02x}", byte).into_bump_str(),
+                    )])
+                    .finish()
+            })
+            .collect::<Vec<_>>();
+
+        let on_hover = self.on_hover.clone();
+        let hover_bus = bus.clone();
+        let on_leave = self.on_hover.clone();
+        let leave_bus = bus.clone();
+
+        dodrio::builder::pre(bump)
+            .attr("class", "hex-view")
+            .children(children)
+            .on("mouseover", move |root, vdom, event| {
+                let span = match byte_span(&event) {
+                    None => return,
+                    Some(span) => span,
+                };
+                hover_bus.publish(on_hover(Some(span)), root);
+                vdom.schedule_render();
+            })
+            .on("mouseout", move |root, vdom, _event| {
+                leave_bus.publish(on_leave(None), root);
+                vdom.schedule_render();
+            })
+            .finish()
+    }
+}
+
+fn byte_span(event: &web_sys::Event) -> Option<(usize, usize)> {
+    use wasm_bindgen::JsCast;
+
+    let target = event.target()?.dyn_into::<web_sys::Element>().ok()?;
+    let start = target.get_attribute("data-start")?.parse().ok()?;
+    let end = target.get_attribute("data-end")?.parse().ok()?;
+    Some((start, end))
+}
+
+impl<'a, Message> From<HexView<Message>> for Element<'a, Message>
+where
+    Message: 'static + Clone,
+{
+    fn from(hex_view: HexView<Message>) -> Element<'a, Message> {
+        Element::new(hex_view)
+    }
+}
@@ -0,0 +1,21 @@
+mod file_drop;
+mod hex_view;
+mod link;
+mod pre;
+mod text_area;
+
+pub use self::file_drop::FileDrop;
+pub use self::hex_view::HexView;
+pub use self::link::Link;
+pub use self::pre::Pre;
+pub use self::text_area::TextArea;
+
+/// Whether `inner` lies fully within `outer`, used for cross-highlighting:
+/// a hex byte's own (always 1-byte) span is checked against the hovered
+/// range, while a diagnostic segment's (often much wider) span is checked
+/// the other way around, against whether it's the hovered range that's
+/// contained in it. Either way it's containment, never exact equality, so
+/// hovering a multi-byte value highlights every byte it decoded from.
+fn span_within(inner: (usize, usize), outer: (usize, usize)) -> bool {
+    outer.0 <= inner.0 && inner.1 <= outer.1
+}
@@ -1,42 +1,144 @@
+use std::rc::Rc;
+
 use bumpalo::Bump;
 use dodrio::Node;
 use iced_web::{style::Sheet, Bus, Element, Widget};
 
-pub struct Pre {
-    content: String,
+use crate::diag::Segment;
+
+enum Content {
+    Plain(String),
+    Segments(Vec<Segment>),
 }
 
-impl Pre {
+/// Renders pre-formatted content. When built from [`Segment`]s (as the
+/// diagnostic pane is), each span-tagged segment becomes its own
+/// `data-start`/`data-end`-tagged `<span>`, so an `on_hover` callback can be
+/// wired up here the same way [`super::HexView`] wires up its own byte
+/// spans, and `highlight` can restyle the segment under the other pane's
+/// hovered range.
+pub struct Pre<Message> {
+    content: Content,
+    highlight: Option<(usize, usize)>,
+    on_hover: Option<Rc<Box<dyn Fn(Option<(usize, usize)>) -> Message>>>,
+}
+
+impl<Message> Pre<Message> {
     pub fn new(content: impl Into<String>) -> Self {
         Self {
-            content: content.into(),
+            content: Content::Plain(content.into()),
+            highlight: None,
+            on_hover: None,
+        }
+    }
+
+    pub fn segmented(segments: Vec<Segment>) -> Self {
+        Self {
+            content: Content::Segments(segments),
+            highlight: None,
+            on_hover: None,
         }
     }
+
+    pub fn on_hover(mut self, on_hover: impl Fn(Option<(usize, usize)>) -> Message + 'static) -> Self {
+        self.on_hover = Some(Rc::new(Box::new(on_hover)));
+        self
+    }
+
+    pub fn highlight(mut self, range: Option<(usize, usize)>) -> Self {
+        self.highlight = range;
+        self
+    }
 }
 
-impl<'a, Message> Widget<Message> for Pre
+impl<Message> Widget<Message> for Pre<Message>
 where
     Message: 'static + Clone,
 {
     fn node<'b>(
         &self,
         bump: &'b Bump,
-        _bus: &Bus<Message>,
+        bus: &Bus<Message>,
         _style_sheet: &mut Sheet<'b>,
     ) -> Node<'b> {
-        let content = bumpalo::format!(in bump, "{}", self.content);
+        let children = match &self.content {
+            Content::Plain(text) => {
+                vec![dodrio::builder::text(bumpalo::format!(in bump, "{}", text).into_bump_str())]
+            }
+            Content::Segments(segments) => segments
+                .iter()
+                .map(|segment| {
+                    let text = dodrio::builder::text(
+                        bumpalo::format!(in bump, "{}", segment.text).into_bump_str(),
+                    );
+
+                    match segment.span {
+                        None => text,
+                        Some((start, end)) => {
+                            let highlighted = self
+                                .highlight
+                                .map_or(false, |range| super::span_within(range, (start, end)));
+                            let class = match (segment.class, highlighted) {
+                                (Some(class), true) => {
+                                    bumpalo::format!(in bump, "{} highlighted", class).into_bump_str()
+                                }
+                                (Some(class), false) => class,
+                                (None, true) => "highlighted",
+                                (None, false) => "",
+                            };
+                            dodrio::builder::span(bump)
+                                .attr("class", class)
+                                .attr("data-start", bumpalo::format!(in bump, "{}", start).into_bump_str())
+                                .attr("data-end", bumpalo::format!(in bump, "{}", end).into_bump_str())
+                                .children(vec![text])
+                                .finish()
+                        }
+                    }
+                })
+                .collect(),
+        };
+
+        let mut node = dodrio::builder::pre(bump).children(children);
+
+        if let Some(on_hover) = self.on_hover.clone() {
+            let hover_bus = bus.clone();
+            let on_leave = on_hover.clone();
+            let leave_bus = bus.clone();
 
-        dodrio::builder::pre(bump)
-            .children(vec![dodrio::builder::text(content.into_bump_str())])
-            .finish()
+            node = node
+                .on("mouseover", move |root, vdom, event| {
+                    let span = match span_under(&event) {
+                        None => return,
+                        Some(span) => span,
+                    };
+                    hover_bus.publish(on_hover(Some(span)), root);
+                    vdom.schedule_render();
+                })
+                .on("mouseout", move |root, vdom, _event| {
+                    leave_bus.publish(on_leave(None), root);
+                    vdom.schedule_render();
+                });
+        }
+
+        node.finish()
     }
 }
 
-impl<'a, Message> From<Pre> for Element<'a, Message>
+fn span_under(event: &web_sys::Event) -> Option<(usize, usize)> {
+    use wasm_bindgen::JsCast;
+
+    let target = event.target()?.dyn_into::<web_sys::Element>().ok()?;
+    let target = target.closest("[data-start]").ok()??;
+    let start = target.get_attribute("data-start")?.parse().ok()?;
+    let end = target.get_attribute("data-end")?.parse().ok()?;
+    Some((start, end))
+}
+
+impl<'a, Message> From<Pre<Message>> for Element<'a, Message>
 where
     Message: 'static + Clone,
 {
-    fn from(pre: Pre) -> Element<'a, Message> {
+    fn from(pre: Pre<Message>) -> Element<'a, Message> {
         Element::new(pre)
     }
 }